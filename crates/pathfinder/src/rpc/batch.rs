@@ -0,0 +1,242 @@
+//! Support for executing a JSON-RPC _batch_ request: an array of call objects sent in a single
+//! frame, as heavy clients use to pipeline many `getStorageAt` / `call` requests rather than
+//! round-tripping one call at a time.
+
+use std::sync::Arc;
+
+use futures::stream::{FuturesUnordered, StreamExt};
+use jsonrpsee::core::JsonValue;
+use jsonrpsee::types::error::{ErrorCode, INVALID_REQUEST_CODE};
+use jsonrpsee::types::ErrorObject;
+use serde::Deserialize;
+use tokio::sync::Semaphore;
+
+use super::v02::RpcContext;
+
+/// Batch-related limits, configured once on the server builder alongside the other listener
+/// settings.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchConfig {
+    /// Maximum number of calls allowed in a single batch. A batch over this size is rejected
+    /// outright with a single error response rather than partially executed.
+    pub max_batch_size: usize,
+    /// Maximum number of calls from a single batch that may execute concurrently.
+    pub max_concurrent_requests: usize,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 100,
+            max_concurrent_requests: 16,
+        }
+    }
+}
+
+/// One entry of a JSON-RPC batch array.
+///
+/// jsonrpsee's own `Request` always carries an `Id`, which can't distinguish "no id field" (a
+/// notification) from some other representation -- so batch entries are parsed through this
+/// plain struct instead, and [BatchEntry::is_notification] is just "no `id` was present".
+#[derive(Debug, Clone, Deserialize)]
+struct BatchEntry<'a> {
+    #[serde(default, deserialize_with = "deserialize_present_id")]
+    id: Option<serde_json::Value>,
+    method: String,
+    #[serde(borrow, default)]
+    params: Option<&'a serde_json::value::RawValue>,
+}
+
+impl BatchEntry<'_> {
+    fn is_notification(&self) -> bool {
+        self.id.is_none()
+    }
+}
+
+/// Only called when an `id` field is present, so an explicit `"id": null` becomes
+/// `Some(Value::Null)` rather than collapsing into the missing-field `None`.
+fn deserialize_present_id<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<serde_json::Value>, D::Error> {
+    serde_json::Value::deserialize(deserializer).map(Some)
+}
+
+/// Rejects a batch whose size exceeds `config.max_batch_size`, with a single JSON-RPC error
+/// standing in for the whole batch.
+fn check_batch_size(len: usize, config: &BatchConfig) -> Result<(), ErrorObject<'static>> {
+    if len > config.max_batch_size {
+        return Err(ErrorObject::owned(
+            INVALID_REQUEST_CODE,
+            format!(
+                "Batch of {len} requests exceeds the maximum allowed size of {}",
+                config.max_batch_size
+            ),
+            None::<()>,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Wraps a successful call's result in a full `{"jsonrpc", "id", "result"}` envelope, so batch
+/// responses can be matched back up to their request by `id` the same way a single call's
+/// response would be.
+fn success_envelope(id: serde_json::Value, result: JsonValue) -> JsonValue {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "result": result,
+    })
+}
+
+/// As [success_envelope], but for a failed call -- every non-notification call gets exactly one
+/// response entry, success or error, per JSON-RPC 2.0.
+fn error_envelope(id: serde_json::Value, error: ErrorObject<'static>) -> JsonValue {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": error,
+    })
+}
+
+/// Parses `raw_batch` (a JSON-RPC batch, i.e. a JSON array of call/notification objects) and
+/// executes every entry against `module`, bounding concurrency with a semaphore sized by
+/// [BatchConfig::max_concurrent_requests] instead of spawning one task per call.
+///
+/// Every non-notification entry produces exactly one `{jsonrpc, id, result | error}` envelope in
+/// the returned array, with its `id` carried through from the request so the client can match
+/// responses back up; a notification (no `id`) is still executed for its side effects but never
+/// produces an entry. Returns `None` when the batch contained only notifications, per JSON-RPC
+/// 2.0: such a batch gets no response body at all.
+pub async fn execute_batch(
+    module: Arc<jsonrpsee::RpcModule<RpcContext>>,
+    raw_batch: &str,
+    config: BatchConfig,
+) -> Result<Option<Vec<JsonValue>>, ErrorObject<'static>> {
+    let entries: Vec<BatchEntry> = serde_json::from_str(raw_batch).map_err(|e| {
+        ErrorObject::owned(INVALID_REQUEST_CODE, format!("Invalid batch: {e}"), None::<()>)
+    })?;
+
+    check_batch_size(entries.len(), &config)?;
+
+    let semaphore = Arc::new(Semaphore::new(config.max_concurrent_requests.max(1)));
+    let mut calls = FuturesUnordered::new();
+
+    for entry in entries {
+        let is_notification = entry.is_notification();
+        let id = entry.id;
+        let method = entry.method;
+        let params = entry.params.map(|p| p.get().to_owned());
+        let module = module.clone();
+        let semaphore = semaphore.clone();
+
+        calls.push(async move {
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("the semaphore is never closed");
+
+            let result = dispatch_one(&module, &method, params.as_deref()).await;
+            (is_notification, id, method, result)
+        });
+    }
+
+    let mut responses = Vec::new();
+    while let Some((is_notification, id, method, result)) = calls.next().await {
+        if is_notification {
+            if let Err(err) = result {
+                tracing::debug!(%method, ?err, "Notification in batch failed");
+            }
+            continue;
+        }
+
+        let id = id.unwrap_or(serde_json::Value::Null);
+        let envelope = match result {
+            Ok(value) => success_envelope(id, value),
+            Err(err) => {
+                tracing::debug!(%method, ?err, "Batched call failed");
+                error_envelope(id, err)
+            }
+        };
+        responses.push(envelope);
+    }
+
+    Ok((!responses.is_empty()).then_some(responses))
+}
+
+async fn dispatch_one(
+    module: &jsonrpsee::RpcModule<RpcContext>,
+    method: &str,
+    params: Option<&str>,
+) -> Result<JsonValue, ErrorObject<'static>> {
+    let params = match params {
+        Some(params) => serde_json::value::RawValue::from_string(params.to_owned())
+            .map_err(|e| to_error_object(format!("Invalid params: {e}")))?,
+        None => serde_json::value::RawValue::from_string("[]".to_owned())
+            .expect("`[]` is valid JSON"),
+    };
+
+    module
+        .call(method, params)
+        .await
+        .map_err(to_error_object)
+}
+
+fn to_error_object(error: impl std::fmt::Display) -> ErrorObject<'static> {
+    ErrorObject::owned(ErrorCode::InternalError.code(), error.to_string(), None::<()>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_with_id_is_not_a_notification() {
+        let entry: BatchEntry =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","method":"chainId","id":1}"#).unwrap();
+        assert!(!entry.is_notification());
+    }
+
+    #[test]
+    fn entry_without_id_is_a_notification() {
+        let entry: BatchEntry =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","method":"chainId"}"#).unwrap();
+        assert!(entry.is_notification());
+    }
+
+    #[test]
+    fn batch_within_limit_is_accepted() {
+        let config = BatchConfig {
+            max_batch_size: 2,
+            ..Default::default()
+        };
+        assert!(check_batch_size(2, &config).is_ok());
+    }
+
+    #[test]
+    fn batch_over_limit_is_rejected() {
+        let config = BatchConfig {
+            max_batch_size: 2,
+            ..Default::default()
+        };
+        let err = check_batch_size(3, &config).unwrap_err();
+        assert_eq!(err.code(), INVALID_REQUEST_CODE);
+    }
+
+    #[test]
+    fn success_envelope_preserves_id_and_result() {
+        let envelope = success_envelope(serde_json::json!(7), serde_json::json!("0x1"));
+        assert_eq!(envelope["id"], serde_json::json!(7));
+        assert_eq!(envelope["result"], serde_json::json!("0x1"));
+        assert!(envelope.get("error").is_none());
+    }
+
+    #[test]
+    fn error_envelope_preserves_id_and_carries_no_result() {
+        let err = to_error_object("boom");
+        let envelope = error_envelope(serde_json::json!(7), err);
+        assert_eq!(envelope["id"], serde_json::json!(7));
+        assert!(envelope.get("result").is_none());
+        assert_eq!(envelope["error"]["message"], serde_json::json!("boom"));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod batch;
+pub mod error;
+pub mod response;
+pub mod v02;
@@ -0,0 +1,101 @@
+//! Unifies how a registered method's return value becomes the payload the JSON-RPC callback
+//! serializes, so success and (structured) error encoding live in one place instead of the
+//! callback hardcoding a flat `Error: Into<RpcError>` bound.
+
+use super::error::RpcError;
+use jsonrpsee::types::error::{CallError, ErrorObject};
+
+/// Implemented by anything a registered method may return. The blanket impl on `Result<T, E>`
+/// means existing methods keep working unchanged; [ResponsePayload] and bare error types let a
+/// method attach structured `data` to its error instead (e.g. the `revert_error` string on
+/// `TransactionExecutionError`, or the conflicting block hash on a reorg).
+pub trait IntoResponse {
+    fn into_response(self) -> Result<serde_json::Value, jsonrpsee::core::Error>;
+}
+
+/// Serializes `value` into the payload the callback sends back, turning a serialization failure
+/// into a regular internal-error response instead of panicking the request-handling task --
+/// every registered method goes through this, so a method whose `Output` can fail to serialize
+/// (e.g. a non-finite `f64`, or a custom `Serialize` impl that errors) must not take the whole
+/// task down with it.
+fn serialize_output(value: impl serde::Serialize) -> Result<serde_json::Value, jsonrpsee::core::Error> {
+    serde_json::to_value(value).map_err(|e| {
+        jsonrpsee::core::Error::Call(CallError::Custom(ErrorObject::owned(
+            jsonrpsee::types::error::ErrorCode::InternalError.code(),
+            format!("Failed to serialize response: {e}"),
+            None::<()>,
+        )))
+    })
+}
+
+impl<T, E> IntoResponse for Result<T, E>
+where
+    T: serde::Serialize,
+    E: Into<RpcError>,
+{
+    fn into_response(self) -> Result<serde_json::Value, jsonrpsee::core::Error> {
+        match self {
+            Ok(output) => serialize_output(output),
+            Err(err) => {
+                let rpc_err: RpcError = err.into();
+                Err(jsonrpsee::core::Error::from(rpc_err))
+            }
+        }
+    }
+}
+
+/// A method's outcome, where the error branch carries an arbitrary, serializable `data` payload
+/// alongside the usual code/message -- the structured counterpart to a flat [RpcError].
+pub enum ResponsePayload<T> {
+    Success(T),
+    Error(RpcError, Option<serde_json::Value>),
+}
+
+impl<T> ResponsePayload<T> {
+    pub fn success(value: T) -> Self {
+        Self::Success(value)
+    }
+
+    pub fn error(err: impl Into<RpcError>) -> Self {
+        Self::Error(err.into(), None)
+    }
+
+    /// As [Self::error], but with a structured `data` payload attached to the error object --
+    /// e.g. a Starknet `revert_error` string or a reorg's conflicting block hash. If `data` fails
+    /// to serialize, it's dropped (with a warning) rather than panicking the request -- it's
+    /// auxiliary context, not the primary error.
+    pub fn error_with_data(err: impl Into<RpcError>, data: impl serde::Serialize) -> Self {
+        let data = serde_json::to_value(data)
+            .map_err(|e| tracing::warn!(error = ?e, "Failed to serialize error data, omitting it"))
+            .ok();
+        Self::Error(err.into(), data)
+    }
+}
+
+impl<T: serde::Serialize> IntoResponse for ResponsePayload<T> {
+    fn into_response(self) -> Result<serde_json::Value, jsonrpsee::core::Error> {
+        match self {
+            Self::Success(value) => serialize_output(value),
+            Self::Error(err, data) => {
+                let error_object: ErrorObject<'static> = err.into();
+                let error_object = match data {
+                    Some(data) => ErrorObject::owned(
+                        error_object.code(),
+                        error_object.message().to_owned(),
+                        Some(data),
+                    ),
+                    None => error_object,
+                };
+                Err(jsonrpsee::core::Error::Call(CallError::Custom(error_object)))
+            }
+        }
+    }
+}
+
+/// Lets a method simply `return err.into()` for an always-failing call without wrapping it in a
+/// `Result`, still going through the same structured encoding path.
+impl IntoResponse for RpcError {
+    fn into_response(self) -> Result<serde_json::Value, jsonrpsee::core::Error> {
+        Err(jsonrpsee::core::Error::from(self))
+    }
+}
@@ -1,6 +1,5 @@
 use std::sync::Arc;
 
-use super::error::RpcError;
 use crate::{core::Chain, state::SyncState};
 use crate::{state::PendingData, storage::Storage};
 
@@ -56,22 +55,23 @@ impl RpcContext {
 /// async fn method(context: Arc<RpcContext>, input: Input) -> Result<Ouput, Error>
 /// ```
 #[allow(dead_code)]
-fn register_method<Input, Output, Error, MethodFuture, Method>(
+fn register_method<Input, MethodFuture, Method>(
     module: &mut jsonrpsee::RpcModule<RpcContext>,
     method_name: &'static str,
     method: Method,
 ) -> anyhow::Result<()>
 where
     Input: ::serde::de::DeserializeOwned + Send + Sync,
-    Output: 'static + ::serde::Serialize + Send + Sync,
-    Error: Into<RpcError>,
-    MethodFuture: std::future::Future<Output = Result<Output, Error>> + Send,
+    MethodFuture: std::future::Future + Send,
+    MethodFuture::Output: super::response::IntoResponse,
     Method: (Fn(RpcContext, Input) -> MethodFuture) + Copy + Send + Sync + 'static,
 {
     use anyhow::Context;
     use jsonrpsee::types::Params;
     use tracing::Instrument;
 
+    use super::response::IntoResponse;
+
     metrics::register_counter!("rpc_method_calls_total", "method" => method_name);
 
     let method_callback = move |params: Params<'static>, context: Arc<RpcContext>| {
@@ -79,10 +79,7 @@ where
         let span = tracing::info_span!("rpc_method", name = method_name);
         async move {
             let input = params.parse::<Input>()?;
-            method((*context).clone(), input).await.map_err(|err| {
-                let rpc_err: RpcError = err.into();
-                jsonrpsee::core::Error::from(rpc_err)
-            })
+            method((*context).clone(), input).await.into_response()
         }
         .instrument(span)
     };
@@ -94,6 +91,60 @@ where
     Ok(())
 }
 
+/// Registers a JSON-RPC subscription pair with the [RpcModule<RpcContext>](jsonrpsee::RpcModule).
+///
+/// `sub_name` is the method a client calls to open the subscription, and is also passed as the
+/// notification method name; `unsub_name` is the method used to tear it down. `method` is
+/// called once per subscription request, after the sink has been accepted, and is expected to
+/// stream serialized `Item`s into it until the client unsubscribes or the future returns.
+///
+/// An example signature for `method` is:
+/// ```ignore
+/// async fn method(context: RpcContext, params: Params, sink: SubscriptionSink) -> anyhow::Result<()>
+/// ```
+fn register_subscription<Params, Item, MethodFuture, Method>(
+    module: &mut jsonrpsee::RpcModule<RpcContext>,
+    sub_name: &'static str,
+    unsub_name: &'static str,
+    method: Method,
+) -> anyhow::Result<()>
+where
+    Params: ::serde::de::DeserializeOwned + Send + Sync + 'static,
+    Item: 'static + ::serde::Serialize + Send + Sync,
+    MethodFuture: std::future::Future<Output = anyhow::Result<()>> + Send + 'static,
+    Method: (Fn(RpcContext, Params, jsonrpsee::SubscriptionSink) -> MethodFuture)
+        + Copy
+        + Send
+        + Sync
+        + 'static,
+{
+    use anyhow::Context;
+
+    metrics::register_counter!("rpc_method_calls_total", "method" => sub_name);
+
+    module
+        .register_subscription(
+            sub_name,
+            sub_name,
+            unsub_name,
+            move |params, sink, context: Arc<RpcContext>| {
+                let context = (*context).clone();
+                let params = params.parse::<Params>()?;
+
+                tokio::spawn(async move {
+                    if let Err(err) = method(context, params, sink).await {
+                        tracing::debug!(name = sub_name, ?err, "Subscription closed with error");
+                    }
+                });
+
+                Ok(())
+            },
+        )
+        .with_context(|| format!("Registering {sub_name}"))?;
+
+    Ok(())
+}
+
 /// Registers a JSON-RPC method with the [RpcModule<RpcContext>](jsonrpsee::RpcModule).
 ///
 /// An example signature for `method` is:
@@ -101,32 +152,27 @@ where
 /// async fn method(context: Arc<RpcContext>) -> Result<Ouput, Error>
 /// ```
 #[allow(dead_code)]
-fn register_method_with_no_input<Output, Error, MethodFuture, Method>(
+fn register_method_with_no_input<MethodFuture, Method>(
     module: &mut jsonrpsee::RpcModule<RpcContext>,
     method_name: &'static str,
     method: Method,
 ) -> anyhow::Result<()>
 where
-    Output: 'static + ::serde::Serialize + Send + Sync,
-    Error: Into<RpcError>,
-    MethodFuture: std::future::Future<Output = Result<Output, Error>> + Send,
+    MethodFuture: std::future::Future + Send,
+    MethodFuture::Output: super::response::IntoResponse,
     Method: (Fn(RpcContext) -> MethodFuture) + Copy + Send + Sync + 'static,
 {
     use anyhow::Context;
     use tracing::Instrument;
 
+    use super::response::IntoResponse;
+
     metrics::register_counter!("rpc_method_calls_total", "method" => method_name);
 
     let method_callback = move |_params, context: Arc<RpcContext>| {
         // why info here? it's the same used in warp tracing filter for example.
         let span = tracing::info_span!("rpc_method", name = method_name);
-        async move {
-            method((*context).clone()).await.map_err(|err| {
-                let rpc_err: RpcError = err.into();
-                jsonrpsee::core::Error::from(rpc_err)
-            })
-        }
-        .instrument(span)
+        async move { method((*context).clone()).await.into_response() }.instrument(span)
     };
 
     module
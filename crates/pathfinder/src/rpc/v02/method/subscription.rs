@@ -0,0 +1,214 @@
+//! Handlers for the `starknet_subscribe*` family of methods.
+//!
+//! Each handler is driven by a [`tokio::sync::broadcast`] channel that the sync loop publishes
+//! to whenever new state lands (see [`crate::state::SyncState`] and
+//! [`crate::state::PendingData`]). The handler's only job is to forward received items into the
+//! [SubscriptionSink] until the client unsubscribes, at which point `send` starts returning
+//! `Ok(false)` and we simply stop. A lagged receiver means the client fell behind and missed
+//! updates, so we close the subscription with an explicit error rather than silently resuming
+//! with a gap.
+
+use jsonrpsee::SubscriptionSink;
+use pathfinder_common::{ContractAddress, EventKey};
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+
+use super::super::{register_subscription, RpcContext};
+
+/// Registers all three `starknet_subscribe*` methods on `module`.
+pub fn register_subscriptions(module: &mut jsonrpsee::RpcModule<RpcContext>) -> anyhow::Result<()> {
+    register_subscription(
+        module,
+        "starknet_subscribeNewHeads",
+        "starknet_unsubscribeNewHeads",
+        subscribe_new_heads,
+    )?;
+    register_subscription(
+        module,
+        "starknet_subscribeEvents",
+        "starknet_unsubscribeEvents",
+        subscribe_events,
+    )?;
+    register_subscription(
+        module,
+        "starknet_subscribePendingTransactions",
+        "starknet_unsubscribePendingTransactions",
+        subscribe_pending_transactions,
+    )?;
+
+    Ok(())
+}
+
+/// `starknet_subscribeNewHeads`: emits every block header as it is committed.
+pub async fn subscribe_new_heads(
+    context: RpcContext,
+    _params: (),
+    mut sink: SubscriptionSink,
+) -> anyhow::Result<()> {
+    let mut new_heads = context.sync_status.subscribe_new_heads();
+
+    loop {
+        match new_heads.recv().await {
+            Ok(header) => {
+                if !forward_or_stop(&mut sink, &header)? {
+                    return Ok(());
+                }
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                close_lagged(sink, "newHeads", skipped);
+                return Ok(());
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventSubscriptionParams {
+    pub address: Option<ContractAddress>,
+    #[serde(default)]
+    pub keys: Vec<EventKey>,
+}
+
+/// `starknet_subscribeEvents`: emits each emitted event, optionally filtered by contract
+/// address and/or event keys.
+pub async fn subscribe_events(
+    context: RpcContext,
+    params: EventSubscriptionParams,
+    mut sink: SubscriptionSink,
+) -> anyhow::Result<()> {
+    let mut events = context.sync_status.subscribe_events();
+
+    loop {
+        match events.recv().await {
+            Ok(event) => {
+                if event_matches(&params, &event) && !forward_or_stop(&mut sink, &event)? {
+                    return Ok(());
+                }
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                close_lagged(sink, "events", skipped);
+                return Ok(());
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        }
+    }
+}
+
+/// Whether `event` passes `params`'s address/key filter -- no address and no keys means
+/// everything matches.
+fn event_matches(params: &EventSubscriptionParams, event: &crate::state::EmittedEvent) -> bool {
+    let address_matches = params
+        .address
+        .map_or(true, |address| address == event.from_address);
+    let keys_match = params.keys.is_empty() || params.keys.iter().any(|k| event.keys.contains(k));
+
+    address_matches && keys_match
+}
+
+/// `starknet_subscribePendingTransactions`: emits each transaction as it is added to the
+/// pending block.
+pub async fn subscribe_pending_transactions(
+    context: RpcContext,
+    _params: (),
+    mut sink: SubscriptionSink,
+) -> anyhow::Result<()> {
+    let pending_data = context
+        .pending_data
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("Pending data is not available on this node"))?;
+    let mut pending_transactions = pending_data.subscribe_pending_transactions();
+
+    loop {
+        match pending_transactions.recv().await {
+            Ok(transaction) => {
+                if !forward_or_stop(&mut sink, &transaction)? {
+                    return Ok(());
+                }
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                close_lagged(sink, "pendingTransactions", skipped);
+                return Ok(());
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        }
+    }
+}
+
+/// Sends `item` into `sink`. Returns `Ok(true)` while the subscription is still live, `Ok(false)`
+/// once the client has unsubscribed/disconnected and `sink.send` stops delivering -- callers must
+/// check this and stop forwarding instead of looping on `recv()` forever.
+fn forward_or_stop<T: serde::Serialize>(
+    sink: &mut SubscriptionSink,
+    item: &T,
+) -> anyhow::Result<bool> {
+    Ok(sink.send(item)?)
+}
+
+fn close_lagged(sink: SubscriptionSink, name: &'static str, skipped: u64) {
+    sink.close(jsonrpsee::types::SubscriptionClosedError::new(
+        format!("{name} subscriber lagged behind and skipped {skipped} updates"),
+        None::<()>,
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::EmittedEvent;
+    use pathfinder_common::felt;
+
+    fn event(address: ContractAddress, keys: Vec<EventKey>) -> EmittedEvent {
+        EmittedEvent {
+            from_address: address,
+            keys,
+        }
+    }
+
+    #[test]
+    fn no_filter_matches_everything() {
+        let params = EventSubscriptionParams::default();
+        let event = event(ContractAddress::new_or_panic(felt!("0x1")), vec![]);
+
+        assert!(event_matches(&params, &event));
+    }
+
+    #[test]
+    fn address_filter_rejects_other_addresses() {
+        let params = EventSubscriptionParams {
+            address: Some(ContractAddress::new_or_panic(felt!("0x1"))),
+            keys: vec![],
+        };
+
+        assert!(event_matches(
+            &params,
+            &event(ContractAddress::new_or_panic(felt!("0x1")), vec![])
+        ));
+        assert!(!event_matches(
+            &params,
+            &event(ContractAddress::new_or_panic(felt!("0x2")), vec![])
+        ));
+    }
+
+    #[test]
+    fn key_filter_requires_at_least_one_matching_key() {
+        let wanted = EventKey(felt!("0xaa"));
+        let params = EventSubscriptionParams {
+            address: None,
+            keys: vec![wanted],
+        };
+
+        let matching = event(
+            ContractAddress::new_or_panic(felt!("0x1")),
+            vec![wanted, EventKey(felt!("0xbb"))],
+        );
+        let non_matching = event(
+            ContractAddress::new_or_panic(felt!("0x1")),
+            vec![EventKey(felt!("0xbb"))],
+        );
+
+        assert!(event_matches(&params, &matching));
+        assert!(!event_matches(&params, &non_matching));
+    }
+}
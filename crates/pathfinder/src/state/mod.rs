@@ -0,0 +1,54 @@
+use pathfinder_common::{BlockHeader, ContractAddress, EventKey};
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+pub mod sync;
+
+pub use sync::pending::PendingData;
+
+/// Bound on each broadcast channel below: subscribers that fall this far behind the sync loop
+/// are closed with a lagged error rather than let to buffer forever.
+const BROADCAST_CHANNEL_CAPACITY: usize = 1024;
+
+/// An event emitted by a committed block, as published to `starknet_subscribeEvents`.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmittedEvent {
+    pub from_address: ContractAddress,
+    pub keys: Vec<EventKey>,
+}
+
+/// Tracks the node's sync status and fans out live updates -- new heads and events -- to RPC
+/// subscriptions as the (block) sync loop commits new blocks.
+pub struct SyncState {
+    new_heads: broadcast::Sender<BlockHeader>,
+    events: broadcast::Sender<EmittedEvent>,
+}
+
+impl Default for SyncState {
+    fn default() -> Self {
+        let (new_heads, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (events, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        Self { new_heads, events }
+    }
+}
+
+impl SyncState {
+    /// Subscribes to every block header as it is committed by the sync loop.
+    pub fn subscribe_new_heads(&self) -> broadcast::Receiver<BlockHeader> {
+        self.new_heads.subscribe()
+    }
+
+    /// Subscribes to every event emitted by a newly committed block.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<EmittedEvent> {
+        self.events.subscribe()
+    }
+
+    /// Called by the sync loop once `header` and its `events` have been committed. A `send`
+    /// error just means there are currently no subscribers, which isn't an error for the caller.
+    pub fn notify_new_head(&self, header: BlockHeader, events: impl IntoIterator<Item = EmittedEvent>) {
+        let _ = self.new_heads.send(header);
+        for event in events {
+            let _ = self.events.send(event);
+        }
+    }
+}
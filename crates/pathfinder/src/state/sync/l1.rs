@@ -1,21 +1,144 @@
+use std::time::Duration;
+
 use pathfinder_ethereum::{L1StateUpdate, StarknetEthereumClient};
 use tokio::sync::mpsc::Sender;
 
+/// An event published by the L1 [sync] loop.
+#[derive(Debug, Clone)]
+pub enum L1SyncEvent {
+    /// A new Starknet state update, reported unchanged by `confirmation_depth` consecutive polls.
+    /// Depth is counted in polls, not in Ethereum blocks.
+    Update(L1StateUpdate),
+    /// L1 now reports a lower block number than the last forwarded update, or different content
+    /// for that same block number -- an L1 reorg. Downstream state should roll back to (at least) the last forwarded update.
+    Reorg,
+}
+
+/// Initial and maximum backoff applied after consecutive RPC failures, so a flaky Ethereum
+/// endpoint gets hammered less the longer it stays down.
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
 /// Syncs L1 state updates.
+///
+/// A candidate update is only forwarded once it has been observed unchanged for
+/// `confirmation_depth` consecutive polls (a stand-in for depth in Ethereum blocks, since polls
+/// track L1 head). If L1 ever reports a block number below the last forwarded one, or the same
+/// block number with different content than what we forwarded for it, that's an L1 reorg, published as
+/// [L1SyncEvent::Reorg] so downstream state can roll back rather than silently diverge.
+/// Consecutive RPC failures back off exponentially, capped at [MAX_BACKOFF], instead of
+/// retrying at a fixed interval.
 pub async fn sync(
-    tx_event: Sender<L1StateUpdate>,
+    tx_event: Sender<L1SyncEvent>,
     ethereum_client: StarknetEthereumClient,
-    start_delay: std::time::Duration,
-    poll_interval: std::time::Duration,
+    start_delay: Duration,
+    poll_interval: Duration,
+    confirmation_depth: u64,
 ) -> anyhow::Result<()> {
     tokio::time::sleep(start_delay).await;
 
+    let confirmation_depth = confirmation_depth.max(1);
+    let mut last_forwarded: Option<L1StateUpdate> = None;
+    let mut candidate: Option<(L1StateUpdate, u64)> = None;
+    let mut backoff = MIN_BACKOFF;
+
     loop {
         tokio::time::sleep(poll_interval).await;
 
-        match ethereum_client.get_starknet_state().await {
-            Ok(state) => tx_event.send(state).await?,
-            Err(e) => tracing::error!("L1 call failed: {e:?}"),
+        let update = match ethereum_client.get_starknet_state().await {
+            Ok(update) => {
+                backoff = MIN_BACKOFF;
+                update
+            }
+            Err(e) => {
+                tracing::error!("L1 call failed: {e:?}");
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                continue;
+            }
+        };
+
+        if let Some(last) = &last_forwarded {
+            if update.block_number <= last.block_number {
+                if update.block_number < last.block_number
+                    || is_reorg(&last.block_hash, &last.state_root, &update.block_hash, &update.state_root)
+                {
+                    tracing::warn!(
+                        last_block = %last.block_number,
+                        new_block = %update.block_number,
+                        "L1 reorg detected: L1 no longer agrees with the last state update we forwarded"
+                    );
+                    tx_event.send(L1SyncEvent::Reorg).await?;
+                    last_forwarded = None;
+                }
+                // Otherwise this is an unchanged re-read of what we already forwarded.
+                candidate = None;
+                continue;
+            }
         }
+
+        candidate = Some(match candidate.take() {
+            Some((seen, confirmations)) if seen.block_number == update.block_number => {
+                if is_reorg(&seen.block_hash, &seen.state_root, &update.block_hash, &update.state_root) {
+                    // Same (still-unconfirmed) height, different content -- L1 reorged while we
+                    // were counting confirmations. Restart the count against the new content
+                    // rather than silently keep confirming the stale one.
+                    tracing::warn!(
+                        block_number = %update.block_number,
+                        "L1 reorg detected while awaiting confirmation: restarting confirmation count"
+                    );
+                    (update, 1)
+                } else {
+                    (seen, confirmations + 1)
+                }
+            }
+            _ => (update, 1),
+        });
+
+        let confirmations = candidate.as_ref().expect("just set above").1;
+        if confirmations < confirmation_depth {
+            continue;
+        }
+
+        let confirmed = candidate.take().expect("just checked above").0;
+        tx_event.send(L1SyncEvent::Update(confirmed.clone())).await?;
+        last_forwarded = Some(confirmed);
+    }
+}
+
+/// Whether the same Starknet block number now disagrees on hash or state root with what we saw
+/// for it before -- i.e. L1 was reorged out from under us, rather than just re-reporting the same
+/// content.
+fn is_reorg<Hash: PartialEq, Root: PartialEq>(
+    last_hash: &Hash,
+    last_root: &Root,
+    new_hash: &Hash,
+    new_root: &Root,
+) -> bool {
+    last_hash != new_hash || last_root != new_root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_content_is_not_a_reorg() {
+        assert!(!is_reorg(&"hash-1", &"root-1", &"hash-1", &"root-1"));
+    }
+
+    #[test]
+    fn different_hash_is_a_reorg() {
+        assert!(is_reorg(&"hash-1", &"root-1", &"hash-2", &"root-1"));
+    }
+
+    #[test]
+    fn different_root_is_a_reorg() {
+        assert!(is_reorg(&"hash-1", &"root-1", &"hash-1", &"root-2"));
+    }
+
+    #[test]
+    fn different_hash_and_root_is_a_reorg() {
+        assert!(is_reorg(&"hash-1", &"root-1", &"hash-2", &"root-2"));
     }
 }
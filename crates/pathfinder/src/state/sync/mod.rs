@@ -0,0 +1,2 @@
+pub mod l1;
+pub mod pending;
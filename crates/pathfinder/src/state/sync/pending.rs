@@ -0,0 +1,41 @@
+use pathfinder_common::TransactionHash;
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// Bound on the pending-transactions channel, so a subscriber that falls this far behind is
+/// closed with a lagged error rather than let to buffer forever.
+const BROADCAST_CHANNEL_CAPACITY: usize = 1024;
+
+/// A transaction as it appears in the pending block, before the block it belongs to is closed.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingTransaction {
+    pub hash: TransactionHash,
+}
+
+/// Holds the current pending block/state diff, and fans out each new pending transaction to RPC
+/// subscriptions as it is added by the sync loop.
+#[derive(Clone)]
+pub struct PendingData {
+    pending_transactions: broadcast::Sender<PendingTransaction>,
+}
+
+impl Default for PendingData {
+    fn default() -> Self {
+        let (pending_transactions, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        Self { pending_transactions }
+    }
+}
+
+impl PendingData {
+    /// Subscribes to every transaction as it is added to the pending block.
+    pub fn subscribe_pending_transactions(&self) -> broadcast::Receiver<PendingTransaction> {
+        self.pending_transactions.subscribe()
+    }
+
+    /// Called by the sync loop whenever a new transaction is added to the pending block. A
+    /// `send` error just means there are currently no subscribers, which isn't an error for the
+    /// caller.
+    pub fn notify_pending_transaction(&self, transaction: PendingTransaction) {
+        let _ = self.pending_transactions.send(transaction);
+    }
+}
@@ -0,0 +1,312 @@
+//! The `#[rpc]` attribute macro.
+//!
+//! Attach it to a trait of `async fn` signatures, each annotated with `#[method(name = "...")]`,
+//! to generate the registration glue against an `RpcModule<RpcContext>` (via the existing
+//! `register_method` / `register_method_with_no_input` helpers) and a matching typed client.
+//! This keeps the method surface -- name, params, return type -- as a single source of truth
+//! shared by the server and the client used from integration tests, instead of hand-threading
+//! each method through `register_method` separately.
+//!
+//! ```ignore
+//! #[rpc(server, namespace = "starknet")]
+//! pub trait StarknetRpc {
+//!     #[method(name = "getBlockWithTxHashes")]
+//!     async fn get_block_with_tx_hashes(&self, block_id: BlockId) -> RpcResult<Block>;
+//! }
+//! ```
+//!
+//! expands to the original trait (with the helper attributes stripped), a `StarknetRpcServer`
+//! type exposing one `register_<method_name>(module, impl Fn...)` function per trait method, and
+//! a `StarknetRpcClient` wrapping a `jsonrpsee::core::client::ClientT` with one async method per
+//! trait method.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, FnArg, ItemTrait, Meta, Pat, TraitItem,
+    TraitItemFn,
+};
+
+/// Parsed `#[rpc(server, namespace = "...")]` attribute arguments.
+struct RpcArgs {
+    server: bool,
+    namespace: String,
+}
+
+impl syn::parse::Parse for RpcArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let metas = Punctuated::<Meta, syn::Token![,]>::parse_terminated(input)?;
+        let mut server = false;
+        let mut namespace = None;
+
+        for meta in metas {
+            match meta {
+                Meta::Path(path) if path.is_ident("server") => server = true,
+                Meta::NameValue(nv) if nv.path.is_ident("namespace") => {
+                    namespace = Some(parse_lit_str(&nv.value)?);
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(other, "expected `server` or `namespace = \"...\"`"))
+                }
+            }
+        }
+
+        Ok(RpcArgs {
+            server,
+            namespace: namespace.ok_or_else(|| {
+                syn::Error::new(input.span(), "#[rpc(..)] requires `namespace = \"...\"`")
+            })?,
+        })
+    }
+}
+
+fn parse_lit_str(expr: &syn::Expr) -> syn::Result<String> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(s),
+            ..
+        }) => Ok(s.value()),
+        other => Err(syn::Error::new_spanned(other, "expected a string literal")),
+    }
+}
+
+/// One `#[method(name = "...")]`-annotated trait method, with that attribute stripped off.
+struct RpcMethod {
+    name: String,
+    sig: syn::Signature,
+}
+
+#[proc_macro_attribute]
+pub fn rpc(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as RpcArgs);
+    let item_trait = parse_macro_input!(item as ItemTrait);
+
+    match expand(args, item_trait) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(args: RpcArgs, mut item_trait: ItemTrait) -> syn::Result<TokenStream2> {
+    let trait_ident = item_trait.ident.clone();
+    let server_ident = format_ident!("{trait_ident}Server");
+    let client_ident = format_ident!("{trait_ident}Client");
+    let namespace = &args.namespace;
+
+    let mut methods = Vec::new();
+    for trait_item in item_trait.items.iter_mut() {
+        if let TraitItem::Fn(TraitItemFn { attrs, sig, .. }) = trait_item {
+            let Some(idx) = attrs.iter().position(|a| a.path().is_ident("method")) else {
+                continue;
+            };
+            let attr = attrs.remove(idx);
+            let name = extract_method_name(&attr)?;
+            methods.push(RpcMethod {
+                name,
+                sig: sig.clone(),
+            });
+        }
+    }
+
+    let register_fns = methods.iter().map(|m| generate_register_fn(namespace, m));
+    let client_fns = methods.iter().map(|m| generate_client_fn(namespace, m));
+
+    // `quote!` does not interpolate inside `///` comments, so the generated docs that name the
+    // trait are built as strings and emitted through `#[doc = ...]`.
+    let server_doc = format!(
+        " Registers every method of [`{trait_ident}`] on `module`, using the shared\n \
+         `register_method` / `register_method_with_no_input` machinery."
+    );
+    let client_doc = format!(
+        " Typed client for [`{trait_ident}`], generated from the same method definitions used to\n \
+         register the server side -- kept as the single source of truth for integration tests."
+    );
+
+    let server_impl = if args.server {
+        quote! {
+            #[doc = #server_doc]
+            pub struct #server_ident;
+
+            impl #server_ident {
+                #(#register_fns)*
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        #item_trait
+
+        #server_impl
+
+        #[doc = #client_doc]
+        pub struct #client_ident<'a, C> {
+            client: &'a C,
+        }
+
+        impl<'a, C> #client_ident<'a, C>
+        where
+            C: jsonrpsee::core::client::ClientT + Send + Sync,
+        {
+            pub fn new(client: &'a C) -> Self {
+                Self { client }
+            }
+
+            #(#client_fns)*
+        }
+    })
+}
+
+fn extract_method_name(attr: &syn::Attribute) -> syn::Result<String> {
+    let mut name = None;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("name") {
+            let value = meta.value()?;
+            let lit: syn::LitStr = value.parse()?;
+            name = Some(lit.value());
+            Ok(())
+        } else {
+            Err(meta.error("expected `name = \"...\"`"))
+        }
+    })?;
+
+    name.ok_or_else(|| syn::Error::new_spanned(attr, "#[method(..)] requires `name = \"...\"`"))
+}
+
+/// Non-`&self` arguments of a trait method signature, in order.
+fn params(sig: &syn::Signature) -> impl Iterator<Item = &FnArg> {
+    sig.inputs.iter().filter(|arg| !matches!(arg, FnArg::Receiver(_)))
+}
+
+fn param_idents(sig: &syn::Signature) -> Vec<syn::Ident> {
+    params(sig)
+        .enumerate()
+        .map(|(i, arg)| match arg {
+            FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
+                Pat::Ident(ident) => ident.ident.clone(),
+                _ => format_ident!("p{i}"),
+            },
+            FnArg::Receiver(_) => unreachable!(),
+        })
+        .collect()
+}
+
+fn generate_register_fn(namespace: &str, method: &RpcMethod) -> TokenStream2 {
+    let method_name = format!("{namespace}_{}", method.name);
+    let fn_ident = &method.sig.ident;
+    let register_ident = format_ident!("register_{fn_ident}");
+
+    let param_types: Vec<syn::Type> = params(&method.sig)
+        .map(|arg| match arg {
+            FnArg::Typed(pat_type) => (*pat_type.ty).clone(),
+            FnArg::Receiver(_) => unreachable!("filtered out by `params`"),
+        })
+        .collect();
+
+    // A method with no parameters goes through `register_method_with_no_input`, whose callback
+    // only ever takes the context -- there is no `Input` to deserialize. Everything else's
+    // parameters become a tuple `Input` that jsonrpsee deserializes positionally, matching
+    // `register_method`'s `Fn(RpcContext, Input) -> MethodFuture` bound.
+    if param_types.is_empty() {
+        quote! {
+            pub fn #register_ident<Method, MethodFuture>(
+                module: &mut jsonrpsee::RpcModule<crate::rpc::v02::RpcContext>,
+                method: Method,
+            ) -> anyhow::Result<()>
+            where
+                MethodFuture: std::future::Future + Send,
+                MethodFuture::Output: crate::rpc::response::IntoResponse,
+                Method: Fn(crate::rpc::v02::RpcContext) -> MethodFuture + Copy + Send + Sync + 'static,
+            {
+                crate::rpc::v02::register_method_with_no_input(module, #method_name, method)
+            }
+        }
+    } else {
+        quote! {
+            pub fn #register_ident<Method, MethodFuture>(
+                module: &mut jsonrpsee::RpcModule<crate::rpc::v02::RpcContext>,
+                method: Method,
+            ) -> anyhow::Result<()>
+            where
+                MethodFuture: std::future::Future + Send,
+                MethodFuture::Output: crate::rpc::response::IntoResponse,
+                Method: Fn(crate::rpc::v02::RpcContext, (#(#param_types,)*)) -> MethodFuture
+                    + Copy
+                    + Send
+                    + Sync
+                    + 'static,
+            {
+                crate::rpc::v02::register_method(module, #method_name, method)
+            }
+        }
+    }
+}
+
+fn generate_client_fn(namespace: &str, method: &RpcMethod) -> TokenStream2 {
+    let method_name = format!("{namespace}_{}", method.name);
+    let sig = &method.sig;
+    let fn_ident = &sig.ident;
+    let output = &sig.output;
+    let idents = param_idents(sig);
+    let typed_params = params(sig);
+
+    quote! {
+        pub async fn #fn_ident(&self, #(#typed_params),*) #output {
+            let params = jsonrpsee::rpc_params![#(#idents),*];
+            self.client
+                .request(#method_name, params)
+                .await
+                .map_err(Into::into)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_method_name() {
+        let attr: syn::Attribute = syn::parse_quote!(#[method(name = "getBlockWithTxHashes")]);
+        assert_eq!(
+            extract_method_name(&attr).unwrap(),
+            "getBlockWithTxHashes"
+        );
+    }
+
+    #[test]
+    fn rejects_method_attribute_without_name() {
+        let attr: syn::Attribute = syn::parse_quote!(#[method(other = "x")]);
+        assert!(extract_method_name(&attr).is_err());
+    }
+
+    #[test]
+    fn param_idents_skip_receiver_and_keep_order() {
+        let sig: syn::Signature = syn::parse_quote!(
+            async fn get_storage_at(&self, contract: ContractAddress, key: StorageKey)
+        );
+        let idents: Vec<String> = param_idents(&sig).iter().map(|i| i.to_string()).collect();
+        assert_eq!(idents, vec!["contract", "key"]);
+    }
+
+    #[test]
+    fn expand_strips_method_attribute_and_generates_client_and_server() {
+        let args: RpcArgs = syn::parse_quote!(server, namespace = "starknet");
+        let item_trait: ItemTrait = syn::parse_quote!(
+            pub trait StarknetRpc {
+                #[method(name = "chainId")]
+                async fn chain_id(&self) -> RpcResult<ChainId>;
+            }
+        );
+
+        let expanded = expand(args, item_trait).unwrap().to_string();
+
+        // The helper attribute must not leak into the re-emitted trait.
+        assert!(!expanded.contains("method (name"));
+        assert!(expanded.contains("StarknetRpcServer"));
+        assert!(expanded.contains("StarknetRpcClient"));
+        assert!(expanded.contains("starknet_chainId"));
+    }
+}